@@ -0,0 +1,85 @@
+use std::ops::RangeInclusive;
+
+use pyo3::exceptions::PyIOError;
+use pyo3::prelude::*;
+
+use disky::compression::CompressionType;
+use disky::writer::RecordWriterConfig;
+
+const ZSTD_LEVEL_RANGE: RangeInclusive<i32> = 1..=22;
+const ZSTD_DEFAULT_LEVEL: i32 = 3;
+const LZ4_LEVEL_RANGE: RangeInclusive<i32> = 1..=12;
+const LZ4_DEFAULT_LEVEL: i32 = 1;
+const GZIP_LEVEL_RANGE: RangeInclusive<i32> = 0..=9;
+const GZIP_DEFAULT_LEVEL: i32 = 6;
+
+/// Apply a writer's `compression`/`compression_level` arguments to a
+/// [`RecordWriterConfig`], validating the level against the codec's valid
+/// range so a bad value fails fast with a `PyIOError` instead of surfacing
+/// as an obscure error from the underlying codec.
+///
+/// `disky::compression::CompressionType`'s `Zstd`/`Lz4`/`Gzip` variants are
+/// field-less, so the level can't be carried inside the enum. Instead it's
+/// threaded through as a separate `with_compression_level` call on the
+/// config, the same way `with_task_queue_capacity`, `with_append` and
+/// `with_lock` are each their own single-argument builder method elsewhere
+/// in this crate rather than fields packed onto another type.
+pub fn apply_compression(
+    config: RecordWriterConfig,
+    codec: &str,
+    level: Option<i32>,
+) -> PyResult<RecordWriterConfig> {
+    let (compression_type, level) = match codec {
+        "zstd" => (
+            CompressionType::Zstd,
+            Some(validate_level("zstd", level, ZSTD_LEVEL_RANGE, ZSTD_DEFAULT_LEVEL)?),
+        ),
+        "lz4" => (
+            CompressionType::Lz4,
+            Some(validate_level("lz4", level, LZ4_LEVEL_RANGE, LZ4_DEFAULT_LEVEL)?),
+        ),
+        "gzip" => (
+            CompressionType::Gzip,
+            Some(validate_level("gzip", level, GZIP_LEVEL_RANGE, GZIP_DEFAULT_LEVEL)?),
+        ),
+        "none" => {
+            if level.is_some() {
+                return Err(PyIOError::new_err(
+                    "compression_level is not supported for compression type 'none'",
+                ));
+            }
+            (CompressionType::None, None)
+        }
+        other => {
+            return Err(PyIOError::new_err(format!(
+                "Unsupported compression type: '{}'. Supported types: 'zstd', 'lz4', 'gzip', 'none'",
+                other
+            )));
+        }
+    };
+
+    let config = config.with_compression(compression_type);
+    Ok(match level {
+        Some(level) => config.with_compression_level(level),
+        None => config,
+    })
+}
+
+fn validate_level(
+    codec: &str,
+    level: Option<i32>,
+    range: RangeInclusive<i32>,
+    default: i32,
+) -> PyResult<i32> {
+    match level {
+        None => Ok(default),
+        Some(level) if range.contains(&level) => Ok(level),
+        Some(level) => Err(PyIOError::new_err(format!(
+            "compression_level {} is out of range for '{}'. Valid range is {}..={}",
+            level,
+            codec,
+            range.start(),
+            range.end()
+        ))),
+    }
+}
@@ -6,6 +6,7 @@ mod corruption;
 mod single_threaded;
 mod multi_threaded;
 mod shard_helpers;
+mod compression;
 mod logging;
 
 // Import types and functions from modules
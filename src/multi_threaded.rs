@@ -1,8 +1,12 @@
 use bytes::Bytes;
 use pyo3::exceptions::PyIOError;
 use pyo3::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::fs::File;
+use std::mem;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 use disky::parallel::multi_threaded_reader::MultiThreadedReader;
 use disky::parallel::multi_threaded_writer::{MultiThreadedWriter, MultiThreadedWriterConfig};
@@ -10,10 +14,13 @@ use disky::parallel::reader::DiskyParallelPiece;
 use disky::parallel::sharding::{FileShardLocator, FileSharder, FileSharderConfig, MultiPathShardLocator, RandomMultiPathShardLocator};
 use disky::parallel::writer::{ParallelWriterConfig, ShardingConfig as WriterShardingConfig};
 use disky::writer::RecordWriterConfig;
-use disky::compression::CompressionType;
 
+use crate::compression::apply_compression;
 use crate::corruption::PyCorruptionStrategy;
-use crate::shard_helpers::{create_multi_threaded_reader, string_paths_to_pathbufs};
+use crate::shard_helpers::{
+    create_multi_threaded_reader, distributed_shard_count, string_paths_to_pathbufs,
+    BoundedRandomShardLocator, DistributedShardLocator,
+};
 
 /// Python wrapper for Disky's MultiThreadedWriter
 #[pyclass]
@@ -24,7 +31,7 @@ pub struct PyMultiThreadedWriter {
 #[pymethods]
 impl PyMultiThreadedWriter {
     #[staticmethod]
-    #[pyo3(signature = (dir_path, prefix, num_shards, worker_threads=None, max_bytes_per_writer=None, task_queue_capacity=None, enable_auto_sharding=None, append=None, compression=None))]
+    #[pyo3(signature = (dir_path, prefix, num_shards, worker_threads=None, max_bytes_per_writer=None, task_queue_capacity=None, enable_auto_sharding=None, append=None, compression=None, compression_level=None, lock=None))]
     fn new_with_shards(
         dir_path: &str,
         prefix: &str,
@@ -35,6 +42,8 @@ impl PyMultiThreadedWriter {
         enable_auto_sharding: Option<bool>,
         append: Option<bool>,
         compression: Option<&str>,
+        compression_level: Option<i32>,
+        lock: Option<bool>,
     ) -> PyResult<Self> {
         // Create FileSharderConfig
         let mut sharder_config = FileSharderConfig::new(prefix);
@@ -44,6 +53,14 @@ impl PyMultiThreadedWriter {
             sharder_config = sharder_config.with_append(true);
         }
 
+        // Take an exclusive advisory lock on each shard as it's opened, so two
+        // processes pointed at the same dir_path/prefix fail fast instead of
+        // silently interleaving records. Lock is released when the shard is
+        // closed.
+        if let Some(true) = lock {
+            sharder_config = sharder_config.with_lock(true);
+        }
+
         // Create the FileSharder with the config
         let file_sharder = FileSharder::with_config(PathBuf::from(dir_path), sharder_config);
 
@@ -54,13 +71,11 @@ impl PyMultiThreadedWriter {
             WriterShardingConfig::new(Box::new(file_sharder), num_shards)
         };
 
-        // Create record writer config with compression if specified
+        // Create record writer config with compression if specified. `compression_level`
+        // is checked against the chosen codec's valid range by `apply_compression`; a
+        // `compression_level` given without `compression` is ignored.
         let record_writer_config = match compression {
-            Some("zstd") => RecordWriterConfig::default().with_compression(CompressionType::Zstd),
-            Some("none") => RecordWriterConfig::default().with_compression(CompressionType::None),
-            Some(other) => {
-                return Err(PyIOError::new_err(format!("Unsupported compression type: '{}'. Supported types: 'zstd', 'none'", other)));
-            },
+            Some(codec) => apply_compression(RecordWriterConfig::default(), codec, compression_level)?,
             None => RecordWriterConfig::default(),
         };
 
@@ -171,15 +186,103 @@ impl PyMultiThreadedWriter {
     }
 }
 
+/// Fixed-capacity shuffle buffer used to randomize record order while
+/// streaming, without ever materializing the full dataset in memory.
+///
+/// Records are pulled eagerly from the underlying reader until the buffer is
+/// full; each draw removes a uniformly random element and immediately
+/// refills its slot from the stream, so the buffer stays at `capacity` until
+/// the stream runs dry.
+struct ShuffleBuffer {
+    buffer: Vec<Bytes>,
+    capacity: usize,
+    rng: StdRng,
+    eof: bool,
+}
+
+impl ShuffleBuffer {
+    fn new(capacity: usize, seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        Self {
+            buffer: Vec::with_capacity(capacity),
+            capacity,
+            rng,
+            eof: false,
+        }
+    }
+
+    /// Draw one record through the buffer, pulling from `next_record` (which
+    /// must already skip non-record pieces and return `Ok(None)` on EOF) to
+    /// top the buffer up to capacity before drawing, then immediately
+    /// refilling the freed slot from the stream. Kept independent of the
+    /// real reader so the reservoir-swap logic can be driven deterministically
+    /// in tests.
+    fn draw(
+        &mut self,
+        mut next_record: impl FnMut() -> PyResult<Option<Bytes>>,
+    ) -> PyResult<Option<Bytes>> {
+        while !self.eof && self.buffer.len() < self.capacity {
+            match next_record()? {
+                Some(bytes) => self.buffer.push(bytes),
+                None => self.eof = true,
+            }
+        }
+
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+
+        let index = self.rng.gen_range(0..self.buffer.len());
+
+        if self.eof {
+            return Ok(Some(self.buffer.remove(index)));
+        }
+
+        match next_record()? {
+            Some(bytes) => Ok(Some(mem::replace(&mut self.buffer[index], bytes))),
+            None => {
+                self.eof = true;
+                Ok(Some(self.buffer.remove(index)))
+            }
+        }
+    }
+}
+
+/// Build the optional shuffle buffer for a reader constructor, rejecting
+/// `shuffle_buffer_size=0` instead of silently producing a reader that
+/// returns no records (a zero-capacity buffer never fills, so every read
+/// would short-circuit to `None` even though the stream has data).
+fn new_shuffle_buffer(
+    shuffle_buffer_size: Option<usize>,
+    seed: Option<u64>,
+) -> PyResult<Option<Mutex<ShuffleBuffer>>> {
+    match shuffle_buffer_size {
+        Some(0) => Err(PyIOError::new_err(
+            "shuffle_buffer_size must be greater than 0",
+        )),
+        Some(size) => Ok(Some(Mutex::new(ShuffleBuffer::new(size, seed)))),
+        None => Ok(None),
+    }
+}
+
 /// Python wrapper for Disky's MultiThreadedReader
 #[pyclass]
 pub struct PyMultiThreadedReader {
     pub reader: MultiThreadedReader<File>,
+    shuffle: Option<Mutex<ShuffleBuffer>>,
+    // Only set by `new_distributed`, so callers can detect stragglers when
+    // the shard count isn't evenly divisible by `world_size`.
+    owned_shards: Option<usize>,
 }
 
 #[pymethods]
 impl PyMultiThreadedReader {
     #[staticmethod]
+    #[pyo3(signature = (dir_path, prefix, num_shards, worker_threads=None, queue_size_mb=None, corruption_strategy=None, shuffle_buffer_size=None, seed=None))]
     fn new_with_shards(
         dir_path: &str,
         prefix: &str,
@@ -187,6 +290,8 @@ impl PyMultiThreadedReader {
         worker_threads: Option<usize>,
         queue_size_mb: Option<usize>,
         corruption_strategy: Option<PyCorruptionStrategy>,
+        shuffle_buffer_size: Option<usize>,
+        seed: Option<u64>,
     ) -> PyResult<Self> {
         // Create a FileShardLocator for the sharded files
         let shard_locator = FileShardLocator::new(PathBuf::from(dir_path), prefix)
@@ -201,7 +306,11 @@ impl PyMultiThreadedReader {
             corruption_strategy,
         )?;
 
-        Ok(Self { reader })
+        Ok(Self {
+            reader,
+            shuffle: new_shuffle_buffer(shuffle_buffer_size, seed)?,
+            owned_shards: None,
+        })
     }
     
     /// Count the number of records in a directory with sharded files
@@ -244,16 +353,19 @@ impl PyMultiThreadedReader {
     }
     
     #[staticmethod]
+    #[pyo3(signature = (shard_paths, num_shards, worker_threads=None, queue_size_mb=None, corruption_strategy=None, shuffle_buffer_size=None, seed=None))]
     fn new_with_shard_paths(
         shard_paths: Vec<String>,
         num_shards: usize,
         worker_threads: Option<usize>,
         queue_size_mb: Option<usize>,
         corruption_strategy: Option<PyCorruptionStrategy>,
+        shuffle_buffer_size: Option<usize>,
+        seed: Option<u64>,
     ) -> PyResult<Self> {
         // Convert Vec<String> to Vec<PathBuf>
         let path_bufs = string_paths_to_pathbufs(shard_paths);
-            
+
         // Create a MultiPathShardLocator with the shard paths
         let shard_locator = MultiPathShardLocator::new(path_bufs)
             .map_err(|e| PyIOError::new_err(e.to_string()))?;
@@ -267,9 +379,13 @@ impl PyMultiThreadedReader {
             corruption_strategy,
         )?;
 
-        Ok(Self { reader })
+        Ok(Self {
+            reader,
+            shuffle: new_shuffle_buffer(shuffle_buffer_size, seed)?,
+            owned_shards: None,
+        })
     }
-    
+
     /// Count the number of records in a list of shard paths
     #[staticmethod]
     fn count_records_with_shard_paths(
@@ -312,21 +428,89 @@ impl PyMultiThreadedReader {
     }
     
     #[staticmethod]
+    #[pyo3(signature = (shard_paths, num_shards, worker_threads=None, queue_size_mb=None, corruption_strategy=None, shuffle_buffer_size=None, seed=None, epochs=None))]
     fn new_with_random_shard_paths(
         shard_paths: Vec<String>,
         num_shards: usize,
         worker_threads: Option<usize>,
         queue_size_mb: Option<usize>,
         corruption_strategy: Option<PyCorruptionStrategy>,
+        shuffle_buffer_size: Option<usize>,
+        seed: Option<u64>,
+        epochs: Option<usize>,
     ) -> PyResult<Self> {
         // Convert Vec<String> to Vec<PathBuf>
         let path_bufs = string_paths_to_pathbufs(shard_paths);
-            
-        // Create a RandomMultiPathShardLocator with the shard paths
-        // This will read shards in a randomized order, repeating indefinitely and reshuffling
-        // after each complete pass through all the shards
-        let shard_locator = RandomMultiPathShardLocator::new(path_bufs)
+
+        let reader = if let Some(epochs) = epochs {
+            // Bounded locator: exactly `epochs` passes, reshuffling each pass,
+            // then a real EOF so `for rec in reader` terminates naturally
+            let shard_locator = BoundedRandomShardLocator::new(path_bufs, epochs, seed)?;
+            create_multi_threaded_reader(
+                shard_locator,
+                num_shards,
+                worker_threads,
+                queue_size_mb,
+                corruption_strategy,
+            )?
+        } else {
+            // Create a RandomMultiPathShardLocator with the shard paths
+            // This will read shards in a randomized order, repeating indefinitely and reshuffling
+            // after each complete pass through all the shards
+            let shard_locator = RandomMultiPathShardLocator::new(path_bufs)
+                .map_err(|e| PyIOError::new_err(e.to_string()))?;
+            create_multi_threaded_reader(
+                shard_locator,
+                num_shards,
+                worker_threads,
+                queue_size_mb,
+                corruption_strategy,
+            )?
+        };
+
+        Ok(Self {
+            reader,
+            shuffle: new_shuffle_buffer(shuffle_buffer_size, seed)?,
+            owned_shards: None,
+        })
+    }
+
+    /// Create a reader that only sees the shards owned by `rank` out of
+    /// `world_size` data-parallel workers, so each process reads a disjoint,
+    /// balanced subset of `shard_paths` without filtering paths in Python.
+    #[staticmethod]
+    #[pyo3(signature = (shard_paths, rank, world_size, num_shards, worker_threads=None, queue_size_mb=None, corruption_strategy=None))]
+    fn new_distributed(
+        shard_paths: Vec<String>,
+        rank: usize,
+        world_size: usize,
+        num_shards: usize,
+        worker_threads: Option<usize>,
+        queue_size_mb: Option<usize>,
+        corruption_strategy: Option<PyCorruptionStrategy>,
+    ) -> PyResult<Self> {
+        // Convert Vec<String> to Vec<PathBuf>
+        let path_bufs = string_paths_to_pathbufs(shard_paths);
+
+        let total_shards = path_bufs.len();
+
+        // Create a MultiPathShardLocator over the full shard list, then wrap
+        // it so only this rank's `index % world_size == rank` shards surface.
+        // Validate rank/world_size (via DistributedShardLocator::new) before
+        // computing the owned-shard count below: `distributed_shard_count`
+        // uses `step_by(world_size)`, which panics on `world_size == 0`.
+        let inner_locator = MultiPathShardLocator::new(path_bufs)
             .map_err(|e| PyIOError::new_err(e.to_string()))?;
+        let shard_locator = DistributedShardLocator::new(inner_locator, rank, world_size)?;
+
+        let owned_shards = distributed_shard_count(total_shards, rank, world_size);
+        log::info!(
+            "rank {} of {} owns {} of {} shards",
+            rank,
+            world_size,
+            owned_shards,
+            total_shards
+        );
 
         // Create the multi-threaded reader
         let reader = create_multi_threaded_reader(
@@ -337,11 +521,27 @@ impl PyMultiThreadedReader {
             corruption_strategy,
         )?;
 
-        Ok(Self { reader })
+        Ok(Self {
+            reader,
+            shuffle: None,
+            owned_shards: Some(owned_shards),
+        })
+    }
+
+    /// Number of shards this rank owns, so callers built via `new_distributed`
+    /// can detect stragglers when the shard count isn't evenly divisible by
+    /// `world_size`. `None` for readers built from any other constructor.
+    #[getter]
+    fn owned_shards(&self) -> Option<usize> {
+        self.owned_shards
     }
 
     #[pyo3(name = "next_record")]
     fn py_next_record<'py>(&self, py: Python<'py>) -> PyResult<Option<pyo3_bytes::PyBytes>> {
+        if let Some(shuffle) = &self.shuffle {
+            return self.next_shuffled_record(py, shuffle);
+        }
+
         py.allow_threads(|| {
             // We need to handle ShardFinished markers inside the loop
             // to avoid recursive calls with GIL released
@@ -363,6 +563,109 @@ impl PyMultiThreadedReader {
         })
     }
 
+    /// Read the next record out of the underlying reader, skipping
+    /// `ShardFinished` markers and reporting EOF as `Ok(None)`.
+    fn next_piece(&self) -> PyResult<Option<Bytes>> {
+        loop {
+            match self.reader.read() {
+                Ok(DiskyParallelPiece::Record(bytes)) => return Ok(Some(bytes)),
+                Ok(DiskyParallelPiece::EOF) => return Ok(None),
+                Ok(DiskyParallelPiece::ShardFinished) => continue,
+                Err(e) => return Err(PyIOError::new_err(e.to_string())),
+            }
+        }
+    }
+
+    /// Draw the next record through the shuffle buffer: eagerly top the
+    /// buffer up to capacity, then pop a uniformly random element and
+    /// immediately refill its slot from the stream.
+    fn next_shuffled_record<'py>(
+        &self,
+        py: Python<'py>,
+        shuffle: &Mutex<ShuffleBuffer>,
+    ) -> PyResult<Option<pyo3_bytes::PyBytes>> {
+        py.allow_threads(|| {
+            let mut state = shuffle.lock().unwrap();
+            let record = state.draw(|| self.next_piece())?;
+            Ok(record.map(pyo3_bytes::PyBytes::new))
+        })
+    }
+
+    /// Pull up to `max_records` records (or until `max_bytes` of payload is
+    /// collected, or EOF) in a single `allow_threads` block, amortizing the
+    /// Python/Rust crossing over a whole batch instead of paying it per
+    /// record. An empty list signals end-of-stream. Draws through the
+    /// shuffle buffer (like `next_record` does) when one is configured, so
+    /// the two methods stay consistent when interleaved on the same reader.
+    #[pyo3(name = "next_batch")]
+    #[pyo3(signature = (max_records, max_bytes=None))]
+    fn py_next_batch<'py>(
+        &self,
+        py: Python<'py>,
+        max_records: usize,
+        max_bytes: Option<usize>,
+    ) -> PyResult<Vec<pyo3_bytes::PyBytes>> {
+        if let Some(shuffle) = &self.shuffle {
+            return self.next_shuffled_batch(py, shuffle, max_records, max_bytes);
+        }
+
+        py.allow_threads(|| {
+            let mut batch = Vec::new();
+            let mut bytes_read = 0usize;
+
+            while batch.len() < max_records {
+                match self.reader.read() {
+                    Ok(DiskyParallelPiece::Record(bytes)) => {
+                        bytes_read += bytes.len();
+                        batch.push(pyo3_bytes::PyBytes::new(bytes));
+                        if max_bytes.is_some_and(|limit| bytes_read >= limit) {
+                            break;
+                        }
+                    }
+                    Ok(DiskyParallelPiece::EOF) => break,
+                    Ok(DiskyParallelPiece::ShardFinished) => continue,
+                    Err(e) => return Err(PyIOError::new_err(e.to_string())),
+                }
+            }
+
+            Ok(batch)
+        })
+    }
+
+    /// Batched counterpart to `next_shuffled_record`: draws up to
+    /// `max_records` (or until `max_bytes`/EOF) from the same shuffle buffer
+    /// under a single lock acquisition, so a `next_batch` call behaves like
+    /// `max_records` back-to-back `next_record` calls rather than bypassing
+    /// the buffer.
+    fn next_shuffled_batch<'py>(
+        &self,
+        py: Python<'py>,
+        shuffle: &Mutex<ShuffleBuffer>,
+        max_records: usize,
+        max_bytes: Option<usize>,
+    ) -> PyResult<Vec<pyo3_bytes::PyBytes>> {
+        py.allow_threads(|| {
+            let mut state = shuffle.lock().unwrap();
+            let mut batch = Vec::new();
+            let mut bytes_read = 0usize;
+
+            while batch.len() < max_records {
+                match state.draw(|| self.next_piece())? {
+                    Some(bytes) => {
+                        bytes_read += bytes.len();
+                        batch.push(pyo3_bytes::PyBytes::new(bytes));
+                        if max_bytes.is_some_and(|limit| bytes_read >= limit) {
+                            break;
+                        }
+                    }
+                    None => break,
+                }
+            }
+
+            Ok(batch)
+        })
+    }
+
     #[pyo3(name = "close")]
     fn py_close<'py>(&self, py: Python<'py>) -> PyResult<()> {
         py.allow_threads(|| {
@@ -413,3 +716,76 @@ impl PyMultiThreadedReader {
         Ok(false) // Don't suppress exceptions
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// Feeds `ShuffleBuffer::draw` a fixed sequence of records followed by
+    /// EOF, mirroring what a `next_piece`-style closure over a real reader
+    /// would produce.
+    fn fake_stream(records: Vec<Bytes>) -> impl FnMut() -> PyResult<Option<Bytes>> {
+        let mut queue = VecDeque::from(records);
+        move || Ok(queue.pop_front())
+    }
+
+    fn record(n: u8) -> Bytes {
+        Bytes::from(vec![n])
+    }
+
+    #[test]
+    fn shuffle_buffer_yields_every_input_record_exactly_once() {
+        let input: Vec<Bytes> = (0..50).map(record).collect();
+        let mut buffer = ShuffleBuffer::new(8, Some(42));
+        let mut stream = fake_stream(input.clone());
+
+        let mut drawn = Vec::new();
+        while let Some(bytes) = buffer.draw(&mut stream).unwrap() {
+            drawn.push(bytes);
+        }
+
+        drawn.sort();
+        let mut expected = input;
+        expected.sort();
+        assert_eq!(drawn, expected);
+    }
+
+    #[test]
+    fn shuffle_buffer_is_deterministic_for_a_given_seed() {
+        let input: Vec<Bytes> = (0..20).map(record).collect();
+
+        let mut first = Vec::new();
+        let mut buffer = ShuffleBuffer::new(4, Some(123));
+        let mut stream = fake_stream(input.clone());
+        while let Some(bytes) = buffer.draw(&mut stream).unwrap() {
+            first.push(bytes);
+        }
+
+        let mut second = Vec::new();
+        let mut buffer = ShuffleBuffer::new(4, Some(123));
+        let mut stream = fake_stream(input);
+        while let Some(bytes) = buffer.draw(&mut stream).unwrap() {
+            second.push(bytes);
+        }
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn shuffle_buffer_handles_fewer_records_than_capacity() {
+        let input: Vec<Bytes> = (0..3).map(record).collect();
+        let mut buffer = ShuffleBuffer::new(8, Some(1));
+        let mut stream = fake_stream(input.clone());
+
+        let mut drawn = Vec::new();
+        while let Some(bytes) = buffer.draw(&mut stream).unwrap() {
+            drawn.push(bytes);
+        }
+
+        drawn.sort();
+        let mut expected = input;
+        expected.sort();
+        assert_eq!(drawn, expected);
+    }
+}
@@ -1,7 +1,13 @@
 use pyo3::exceptions::PyIOError;
 use pyo3::prelude::*;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::collections::VecDeque;
 use std::fs::File;
+use std::io;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 use disky::parallel::multi_threaded_reader::{
     MultiThreadedReader, MultiThreadedReaderConfig, ReadingOrder,
@@ -11,6 +17,146 @@ use disky::parallel::sharding::ShardLocator;
 
 use crate::corruption::PyCorruptionStrategy;
 
+/// Wraps an inner [`ShardLocator`] so that only the shards owned by `rank`
+/// out of `world_size` data-parallel workers are exposed, following the
+/// "data row is picked only if count % N == K" convention used by
+/// distributed data loaders.
+///
+/// Shards are still pulled from the inner locator in order; ones that don't
+/// belong to this rank are silently skipped rather than filtering the path
+/// list up front in Python, so this composes with any `ShardLocator`
+/// (directory scans, explicit path lists, ...).
+///
+/// The index bump and the call into `inner` are folded under one lock
+/// (rather than an atomic counter) because under concurrent callers the two
+/// operations must happen atomically: if one thread could bump the counter
+/// while another called `inner.next_shard()` first, the `global_index` tag
+/// would get decorrelated from the physical shard position it's supposed to
+/// describe, corrupting the `i % world_size == rank` partitioning (ranks
+/// could see overlapping or missing shards).
+pub struct DistributedShardLocator<T, L: ShardLocator<T>> {
+    // `inner` and the next global index are behind a single lock so a
+    // caller's index bump and its call into `inner` happen atomically.
+    state: Mutex<(L, usize)>,
+    rank: usize,
+    world_size: usize,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T, L: ShardLocator<T>> DistributedShardLocator<T, L> {
+    pub fn new(inner: L, rank: usize, world_size: usize) -> PyResult<Self> {
+        if rank >= world_size {
+            return Err(PyIOError::new_err(format!(
+                "rank {} must be less than world_size {}",
+                rank, world_size
+            )));
+        }
+
+        Ok(Self {
+            state: Mutex::new((inner, 0)),
+            rank,
+            world_size,
+            _marker: std::marker::PhantomData,
+        })
+    }
+}
+
+impl<T, L: ShardLocator<T> + Send> ShardLocator<T> for DistributedShardLocator<T, L> {
+    fn next_shard(&self) -> io::Result<Option<T>> {
+        loop {
+            let mut state = self.state.lock().unwrap();
+            let (inner, next_global_index) = &mut *state;
+            let global_index = *next_global_index;
+            let shard = inner.next_shard()?;
+            *next_global_index += 1;
+            drop(state);
+
+            match shard {
+                Some(shard) if global_index % self.world_size == self.rank => {
+                    return Ok(Some(shard));
+                }
+                Some(_) => continue,
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Number of shards out of `total_shards` that belong to `rank` under the
+/// `index % world_size == rank` partitioning, so callers can detect
+/// stragglers when `total_shards` is not divisible by `world_size`.
+pub fn distributed_shard_count(total_shards: usize, rank: usize, world_size: usize) -> usize {
+    (rank..total_shards).step_by(world_size).count()
+}
+
+/// Mutable state of a [`BoundedRandomShardLocator`].
+/// `queue` and `epochs_remaining` are folded into a single mutex (rather
+/// than one each) because worker threads race on the epoch boundary: a
+/// thread must see the refilled queue and the decremented count atomically,
+/// or a second thread that also observed an empty queue can read
+/// `epochs_remaining == 0` after another thread already refilled it for the
+/// final pass and wrongly conclude EOF.
+struct BoundedRandomShardLocatorState {
+    queue: VecDeque<PathBuf>,
+    epochs_remaining: usize,
+}
+
+/// A [`ShardLocator`] over a fixed path list that performs exactly `epochs`
+/// complete passes, reshuffling the shard order at the start of each pass,
+/// and then reports EOF by returning `None` so iteration terminates
+/// naturally instead of running forever like [`RandomMultiPathShardLocator`].
+pub struct BoundedRandomShardLocator {
+    paths: Vec<PathBuf>,
+    state: Mutex<BoundedRandomShardLocatorState>,
+    rng: Mutex<StdRng>,
+}
+
+impl BoundedRandomShardLocator {
+    pub fn new(mut paths: Vec<PathBuf>, epochs: usize, seed: Option<u64>) -> PyResult<Self> {
+        if epochs == 0 {
+            return Err(PyIOError::new_err("epochs must be greater than 0"));
+        }
+
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        paths.shuffle(&mut rng);
+        let queue = VecDeque::from(paths.clone());
+
+        Ok(Self {
+            paths,
+            state: Mutex::new(BoundedRandomShardLocatorState {
+                queue,
+                epochs_remaining: epochs - 1,
+            }),
+            rng: Mutex::new(rng),
+        })
+    }
+}
+
+impl ShardLocator<File> for BoundedRandomShardLocator {
+    fn next_shard(&self) -> io::Result<Option<File>> {
+        loop {
+            let mut state = self.state.lock().unwrap();
+            if let Some(path) = state.queue.pop_front() {
+                drop(state);
+                return File::open(&path).map(Some);
+            }
+
+            if state.epochs_remaining == 0 {
+                return Ok(None);
+            }
+            state.epochs_remaining -= 1;
+
+            let mut shuffled = self.paths.clone();
+            shuffled.shuffle(&mut *self.rng.lock().unwrap());
+            state.queue = VecDeque::from(shuffled);
+            // Loop back around and pop from the now-refilled queue.
+        }
+    }
+}
+
 /// Helper function to create a MultiThreadedReader from a shard locator
 pub fn create_multi_threaded_reader<ShardLocatorType>(
     shard_locator: ShardLocatorType,
@@ -79,3 +225,96 @@ pub fn string_paths_to_pathbufs(shard_paths: Vec<String>) -> Vec<PathBuf> {
     shard_paths.into_iter().map(|s| PathBuf::from(s)).collect()
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    /// An in-memory [`ShardLocator`] that hands out items from a fixed list
+    /// in order, so `DistributedShardLocator` can be exercised without
+    /// touching the filesystem.
+    struct VecShardLocator<T> {
+        items: Mutex<VecDeque<T>>,
+    }
+
+    impl<T> VecShardLocator<T> {
+        fn new(items: Vec<T>) -> Self {
+            Self {
+                items: Mutex::new(VecDeque::from(items)),
+            }
+        }
+    }
+
+    impl<T> ShardLocator<T> for VecShardLocator<T> {
+        fn next_shard(&self) -> io::Result<Option<T>> {
+            Ok(self.items.lock().unwrap().pop_front())
+        }
+    }
+
+    #[test]
+    fn distributed_shard_locator_partitions_by_rank() {
+        let world_size = 3;
+        let total = 10;
+
+        for rank in 0..world_size {
+            let locator =
+                DistributedShardLocator::new(VecShardLocator::new((0..total).collect()), rank, world_size)
+                    .unwrap();
+
+            let mut owned = Vec::new();
+            while let Some(shard) = locator.next_shard().unwrap() {
+                owned.push(shard);
+            }
+
+            let expected: Vec<usize> = (rank..total).step_by(world_size).collect();
+            assert_eq!(owned, expected);
+        }
+    }
+
+    #[test]
+    fn distributed_shard_locator_rejects_rank_at_or_above_world_size() {
+        assert!(DistributedShardLocator::new(VecShardLocator::new(vec![1, 2, 3]), 3, 3).is_err());
+        assert!(DistributedShardLocator::new(VecShardLocator::new(vec![1, 2, 3]), 0, 0).is_err());
+    }
+
+    #[test]
+    fn distributed_shard_locator_concurrent_callers_partition_without_overlap_or_loss() {
+        // Drives next_shard() from many threads at once to exercise the race
+        // between bumping the global index and calling into `inner`: if the
+        // two weren't atomic, ranks could see duplicated or dropped shards.
+        let world_size = 4;
+        let total = 997; // prime, so the split is uneven across ranks
+        let locator = Arc::new(
+            DistributedShardLocator::new(VecShardLocator::new((0..total).collect()), 0, world_size)
+                .unwrap(),
+        );
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let locator = Arc::clone(&locator);
+                thread::spawn(move || {
+                    let mut owned = Vec::new();
+                    while let Some(shard) = locator.next_shard().unwrap() {
+                        owned.push(shard);
+                    }
+                    owned
+                })
+            })
+            .collect();
+
+        let mut all: Vec<usize> = handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect();
+        all.sort();
+
+        let expected: Vec<usize> = (0..total).step_by(world_size).collect();
+        assert_eq!(all, expected);
+    }
+
+    #[test]
+    fn bounded_random_shard_locator_rejects_zero_epochs() {
+        assert!(BoundedRandomShardLocator::new(vec![PathBuf::from("a")], 0, Some(1)).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 // No need for Bytes in this module
+use fs2::FileExt;
 use pyo3::exceptions::PyIOError;
 use pyo3::prelude::*;
 use std::fs::File;
@@ -18,8 +19,16 @@ pub struct PyRecordWriter {
 #[pymethods]
 impl PyRecordWriter {
     #[new]
-    fn new(path: &str) -> PyResult<Self> {
+    #[pyo3(signature = (path, lock=None))]
+    fn new(path: &str, lock: Option<bool>) -> PyResult<Self> {
         let file = File::create(Path::new(path)).map_err(|e| PyIOError::new_err(e.to_string()))?;
+
+        if let Some(true) = lock {
+            file.try_lock_exclusive().map_err(|_| {
+                PyIOError::new_err(format!("Could not acquire exclusive lock on '{}'", path))
+            })?;
+        }
+
         let writer = RecordWriter::new(file).map_err(|e| PyIOError::new_err(e.to_string()))?;
 
         Ok(Self { writer })
@@ -120,6 +129,36 @@ impl PyRecordReader {
         }
     }
 
+    /// Pull up to `max_records` records (or until `max_bytes` of payload is
+    /// collected, or EOF) in one call, amortizing the Python/Rust crossing
+    /// over a whole batch instead of paying it per record. An empty list
+    /// signals end-of-stream.
+    #[pyo3(signature = (max_records, max_bytes=None))]
+    fn next_batch(
+        &mut self,
+        max_records: usize,
+        max_bytes: Option<usize>,
+    ) -> PyResult<Vec<pyo3_bytes::PyBytes>> {
+        let mut batch = Vec::new();
+        let mut bytes_read = 0usize;
+
+        while batch.len() < max_records {
+            match self.reader.next_record() {
+                Ok(DiskyPiece::Record(bytes)) => {
+                    bytes_read += bytes.len();
+                    batch.push(pyo3_bytes::PyBytes::new(bytes));
+                    if max_bytes.is_some_and(|limit| bytes_read >= limit) {
+                        break;
+                    }
+                }
+                Ok(DiskyPiece::EOF) => break,
+                Err(e) => return Err(PyIOError::new_err(e.to_string())),
+            }
+        }
+
+        Ok(batch)
+    }
+
     fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
         slf
     }